@@ -0,0 +1,280 @@
+//! Turns a built collection of [`TypeErasedModel`]s into a CLI-dispatched process.
+//!
+//! Extensions speak a simple `proto1` line protocol: `<binary> proto1 <operation> [version]`,
+//! reading a single JSON [`Request`] envelope from stdin and writing the operation's result (or
+//! error) to stdout/stderr.
+use crate::model::{AsTypeErasedModel, Format, SchemaParams, TypeErasedModel};
+use serde::Deserialize;
+use snafu::Snafu;
+use std::io::Read;
+use std::process::ExitCode;
+
+/// A built settings extension, ready to run as the extension binary's `main`.
+#[derive(Debug)]
+pub struct SettingsExtension {
+    name: String,
+    models: Vec<Box<dyn TypeErasedModel>>,
+    lenient_input: bool,
+    format: Format,
+}
+
+/// The JSON envelope extensions read from stdin for every `proto1` operation.
+///
+/// Which fields matter depends on the operation: `input` carries the hand-authored text payload
+/// for `set`/`set-partial`/`validate`, `current` carries the existing value `set`/`set-partial`
+/// are run against (or the partial value already generated, for `generate`), and `related` carries
+/// the other settings the settings system has made available for `generate`/`validate`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Request {
+    input: String,
+    current: Option<serde_json::Value>,
+    related: Option<serde_json::Value>,
+    known_values: std::collections::HashMap<String, Vec<serde_json::Value>>,
+}
+
+impl SettingsExtension {
+    fn model(&self, version: &str) -> Option<&dyn TypeErasedModel> {
+        self.models
+            .iter()
+            .map(AsRef::as_ref)
+            .find(|model| model.get_version() == version)
+    }
+
+    /// Parses `proto1 <operation> [version]` from the process's command-line arguments, reads a
+    /// [`Request`] from stdin, dispatches it to the matching model(s), and prints the result.
+    ///
+    /// Every operation except `schema` requires `version`; `schema` prints the schema for just
+    /// that version if given, or for every registered model version otherwise.
+    pub fn run(&self) -> ExitCode {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        let (Some(proto), Some(operation)) = (args.first(), args.get(1)) else {
+            eprintln!(
+                "usage: {0} proto1 schema [version] | {0} proto1 <set|set-partial|validate|generate|dependencies> <version>",
+                self.name
+            );
+            return ExitCode::FAILURE;
+        };
+        if proto != "proto1" {
+            eprintln!("unsupported protocol '{proto}'");
+            return ExitCode::FAILURE;
+        }
+        let version = args.get(2).map(String::as_str);
+
+        let mut raw_request = String::new();
+        if let Err(e) = std::io::stdin().read_to_string(&mut raw_request) {
+            eprintln!("failed to read request from stdin: {e}");
+            return ExitCode::FAILURE;
+        }
+        let request: Request = if raw_request.trim().is_empty() {
+            Request::default()
+        } else {
+            match serde_json::from_str(&raw_request) {
+                Ok(request) => request,
+                Err(e) => {
+                    eprintln!("failed to parse request envelope: {e}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        };
+
+        if operation == "schema" {
+            return self.run_schema(version, &request);
+        }
+
+        let Some(version) = version else {
+            eprintln!("operation '{operation}' requires a version argument");
+            return ExitCode::FAILURE;
+        };
+        let Some(model) = self.model(version) else {
+            eprintln!(
+                "'{}' has no model registered for version '{version}'",
+                self.name
+            );
+            return ExitCode::FAILURE;
+        };
+
+        // `set`/`set-partial` hand back the setting's own value, so they're encoded in whichever
+        // `Format` this extension was built with. Every other operation's result is an envelope
+        // around that value (a bool, a `GenerateResult`, a JSON Schema) rather than the value
+        // itself, and isn't necessarily representable in `self.format` — TOML in particular has no
+        // bare scalars or nulls — so those are always reported as JSON.
+        let output: Result<String, String> = match operation.as_str() {
+            "set" => model
+                .set(
+                    request.current,
+                    &request.input,
+                    self.format,
+                    self.lenient_input,
+                )
+                .map_err(|e| e.to_string())
+                .and_then(|value| self.format.encode(&value).map_err(|e| e.to_string())),
+            "set-partial" => model
+                .set_partial(
+                    request.current,
+                    &request.input,
+                    self.format,
+                    self.lenient_input,
+                )
+                .map_err(|e| e.to_string())
+                .and_then(|value| self.format.encode(&value).map_err(|e| e.to_string())),
+            "validate" => model
+                .validate(
+                    &request.input,
+                    self.format,
+                    self.lenient_input,
+                    request.related,
+                )
+                .map_err(|e| e.to_string())
+                .and_then(|value| serde_json::to_string_pretty(&value).map_err(|e| e.to_string())),
+            "generate" => model
+                .generate(request.current, request.related)
+                .map_err(|e| e.to_string())
+                .and_then(|result| {
+                    serde_json::to_string_pretty(&result).map_err(|e| e.to_string())
+                }),
+            "dependencies" => {
+                serde_json::to_string_pretty(model.dependencies()).map_err(|e| e.to_string())
+            }
+            other => Err(format!("unknown operation '{other}'")),
+        };
+
+        match output {
+            Ok(encoded) => {
+                println!("{encoded}");
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                ExitCode::FAILURE
+            }
+        }
+    }
+
+    /// Handles the `schema` operation, which (unlike every other operation) accepts an optional
+    /// `version`: given one, only that model's schema is printed; given none, every registered
+    /// model's schema is printed. Either way the output is a JSON object keyed by version.
+    fn run_schema(&self, version: Option<&str>, request: &Request) -> ExitCode {
+        let params = SchemaParams {
+            known_values: request.known_values.clone(),
+        };
+
+        let models: Vec<&dyn TypeErasedModel> = match version {
+            Some(version) => match self.model(version) {
+                Some(model) => vec![model],
+                None => {
+                    eprintln!(
+                        "'{}' has no model registered for version '{version}'",
+                        self.name
+                    );
+                    return ExitCode::FAILURE;
+                }
+            },
+            None => self.models.iter().map(AsRef::as_ref).collect(),
+        };
+
+        let schemas: Result<
+            std::collections::BTreeMap<&str, schemars::schema::RootSchema>,
+            String,
+        > = models
+            .into_iter()
+            .map(|model| {
+                model
+                    .schema(params.clone())
+                    .map(|schema| (model.get_version(), schema))
+                    .map_err(|e| e.to_string())
+            })
+            .collect();
+
+        match schemas
+            .and_then(|schemas| serde_json::to_string_pretty(&schemas).map_err(|e| e.to_string()))
+        {
+            Ok(encoded) => {
+                println!("{encoded}");
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                ExitCode::FAILURE
+            }
+        }
+    }
+}
+
+/// Builds a [`SettingsExtension`] with no migration support between model versions — suitable for
+/// an extension's very first version, or one where the settings system manages migration itself.
+#[derive(Debug)]
+pub struct NullMigratorExtensionBuilder {
+    name: String,
+    models: Vec<Box<dyn TypeErasedModel>>,
+    lenient_input: bool,
+    format: Format,
+}
+
+impl NullMigratorExtensionBuilder {
+    /// Starts building an extension named `name`, as it's registered with the settings system.
+    pub fn with_name(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            models: Vec::new(),
+            lenient_input: false,
+            format: Format::Json,
+        }
+    }
+
+    /// Registers the versions of the settings model this extension serves, e.g. as returned by
+    /// [`BottlerocketSetting::model`](crate::BottlerocketSetting::model).
+    pub fn with_models(mut self, models: Vec<Box<dyn AsTypeErasedModel>>) -> Self {
+        self.models = models
+            .into_iter()
+            .map(AsTypeErasedModel::as_erased_model)
+            .collect();
+        self
+    }
+
+    /// Allows hand-authored JSON input (to `set`/`set-partial`/`validate`) to use JSONC-style
+    /// comments and trailing commas, instead of requiring strict JSON.
+    ///
+    /// Defaults to `false`.
+    pub fn lenient_input(mut self, lenient_input: bool) -> Self {
+        self.lenient_input = lenient_input;
+        self
+    }
+
+    /// Sets the wire format of the `input` payload and of the values `set`/`set-partial` print.
+    ///
+    /// The request envelope on stdin, and the results of `validate`, `generate`, `dependencies`
+    /// and `schema`, are always JSON regardless of this setting.
+    ///
+    /// Defaults to [`Format::Json`].
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Finishes building the extension.
+    pub fn build(self) -> Result<SettingsExtension, BuildExtensionError> {
+        if self.models.is_empty() {
+            return NoModelsSnafu { name: self.name }.fail();
+        }
+
+        Ok(SettingsExtension {
+            name: self.name,
+            models: self.models,
+            lenient_input: self.lenient_input,
+            format: self.format,
+        })
+    }
+}
+
+/// The error type returned when building a [`SettingsExtension`] fails.
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum BuildExtensionError {
+    /// Returned when a [`NullMigratorExtensionBuilder`] is built without registering any models.
+    #[snafu(display("Settings extension '{}' was built with no models", name))]
+    NoModels {
+        /// The name the extension was given via `with_name`.
+        name: String,
+    },
+}
@@ -0,0 +1,15 @@
+//! The Bottlerocket settings SDK.
+//!
+//! This crate provides the [`SettingsModel`] trait for describing a setting's shape and behavior,
+//! and [`SettingsExtension`]/[`NullMigratorExtensionBuilder`] for turning a collection of
+//! [`BottlerocketSetting`] versions into a runnable extension binary.
+#![deny(missing_docs)]
+
+mod extension;
+mod model;
+
+pub use extension::{BuildExtensionError, NullMigratorExtensionBuilder, SettingsExtension};
+pub use model::{
+    AsTypeErasedModel, BottlerocketSetting, BottlerocketSettingError, Dependencies, Dependency,
+    Format, GenerateResult, SchemaParams, SettingsModel, SettingsModelSchema, TypeErasedModel,
+};
@@ -0,0 +1,130 @@
+//! Declarative, typed dependencies between settings models.
+use super::{
+    error::{DependencyDeserializeSnafu, MissingDependencySnafu},
+    BottlerocketSettingError,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use snafu::{OptionExt, ResultExt};
+
+/// Declares that a [`SettingsModel`](super::SettingsModel) depends on a specific version of
+/// another settings extension, e.g. to compute its own values during `generate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Dependency {
+    /// The name of the extension this model depends on, e.g. `"kubernetes"`.
+    pub extension: &'static str,
+    /// The version of that extension's settings model this model was written against, e.g.
+    /// `"v1"`.
+    pub version: &'static str,
+}
+
+impl Dependency {
+    /// Declares a dependency on the given `version` of `extension`.
+    pub const fn new(extension: &'static str, version: &'static str) -> Self {
+        Self { extension, version }
+    }
+}
+
+/// A typed view over the `dependent_settings`/`validated_settings` map the settings system passes
+/// to [`SettingsModel::generate`](super::SettingsModel::generate) and
+/// [`SettingsModel::validate`](super::SettingsModel::validate), keyed by extension name.
+///
+/// This centralizes the JSON digging that's otherwise duplicated across every model that depends
+/// on another extension's values, e.g. a Pluto-style model computing Kubernetes values from other
+/// settings.
+#[derive(Debug, Default, Clone)]
+pub struct Dependencies(serde_json::Map<String, serde_json::Value>);
+
+impl Dependencies {
+    /// Wraps the raw map the settings system provides, ready for typed lookups.
+    pub(super) fn new(settings: Option<serde_json::Value>) -> Self {
+        Self(
+            settings
+                .and_then(|settings| settings.as_object().cloned())
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Deserializes the value provided for `dependency`.
+    ///
+    /// Fails with [`BottlerocketSettingError::MissingDependency`] if the settings system hasn't
+    /// provided a value for it yet, or [`BottlerocketSettingError::DependencyDeserialize`] if the
+    /// value doesn't match `T`.
+    pub fn get<T: DeserializeOwned>(
+        &self,
+        dependency: &Dependency,
+    ) -> Result<T, BottlerocketSettingError> {
+        let value = self
+            .0
+            .get(dependency.extension)
+            .cloned()
+            .context(MissingDependencySnafu {
+                extension: dependency.extension,
+                version: dependency.version,
+            })?;
+
+        serde_json::from_value(value).context(DependencyDeserializeSnafu {
+            extension: dependency.extension,
+            version: dependency.version,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    const KUBERNETES_V1: Dependency = Dependency::new("kubernetes", "v1");
+
+    #[test]
+    fn get_missing_dependency_fails() {
+        let dependencies = Dependencies::new(Some(json!({})));
+        let err = dependencies
+            .get::<serde_json::Value>(&KUBERNETES_V1)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            BottlerocketSettingError::MissingDependency { extension, version }
+                if extension == "kubernetes" && version == "v1"
+        ));
+    }
+
+    #[test]
+    fn get_mistyped_dependency_fails_to_deserialize() {
+        let dependencies = Dependencies::new(Some(json!({"kubernetes": "not-an-object"})));
+        let err = dependencies
+            .get::<serde_json::Map<String, serde_json::Value>>(&KUBERNETES_V1)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            BottlerocketSettingError::DependencyDeserialize { extension, version, .. }
+                if extension == "kubernetes" && version == "v1"
+        ));
+    }
+
+    #[test]
+    fn get_present_dependency_deserializes() {
+        let dependencies = Dependencies::new(Some(json!({"kubernetes": {"max_pods": 5}})));
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct K8s {
+            max_pods: u32,
+        }
+        assert_eq!(
+            dependencies.get::<K8s>(&KUBERNETES_V1).unwrap(),
+            K8s { max_pods: 5 }
+        );
+    }
+
+    #[test]
+    fn no_settings_provided_behaves_like_empty_map() {
+        let dependencies = Dependencies::new(None);
+        let err = dependencies
+            .get::<serde_json::Value>(&KUBERNETES_V1)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            BottlerocketSettingError::MissingDependency { .. }
+        ));
+    }
+}
@@ -0,0 +1,285 @@
+//! Type erasure for [`SettingsModel`].
+//!
+//! Each version of a setting has its own concrete `Self`, [`PartialKind`](SettingsModel::PartialKind),
+//! and [`ErrorKind`](SettingsModel::ErrorKind). [`TypeErasedModel`] erases all of these behind a
+//! single dynamic interface operating on [`serde_json::Value`], so that a
+//! [`SettingsExtension`](crate::SettingsExtension) can hold many model versions in a single
+//! `Vec<Box<dyn TypeErasedModel>>`.
+use super::{
+    dependency::Dependencies,
+    error::{
+        DeserializeInputSnafu, GenerateSettingSnafu, NoSchemaSnafu, ParseSettingSnafu,
+        SerializeResultSnafu, SetSettingSnafu, ValidateSettingSnafu,
+    },
+    merge::merge_non_null_json_value_into,
+    schema::SchemaParams,
+    BottlerocketSetting, BottlerocketSettingError, Dependency, Format, GenerateResult,
+    SettingsModel,
+};
+use snafu::{OptionExt, ResultExt};
+use std::fmt::Debug;
+
+/// The wire format in which [`crate::model`] stores values internally, independent of whatever
+/// [`Format`] the extension's caller used for its own input and output.
+const INTERNAL_FORMAT: Format = Format::Json;
+
+/// A [`SettingsModel`] whose types have been erased to [`serde_json::Value`], so that many
+/// versions of a setting can be driven through a single dynamic interface.
+pub trait TypeErasedModel: Debug {
+    /// See [`SettingsModel::get_version`].
+    fn get_version(&self) -> &'static str;
+
+    /// See [`SettingsModel::dependencies`].
+    ///
+    /// Exposed on the erased interface so the settings system can read a model's dependency
+    /// graph (to order `generate` cycles) without knowing its concrete type.
+    fn dependencies(&self) -> &'static [Dependency];
+
+    /// See [`SettingsModel::set`], with `current_value` already deserialized and `target` as the
+    /// raw text given to the extension, encoded in `format`.
+    ///
+    /// When `lenient` is set and `format` is [`Format::Json`], `target` may use JSONC-style
+    /// comments and trailing commas; see [`super::input::parse_value`].
+    fn set(
+        &self,
+        current_value: Option<serde_json::Value>,
+        target: &str,
+        format: Format,
+        lenient: bool,
+    ) -> Result<serde_json::Value, BottlerocketSettingError>;
+
+    /// See [`SettingsModel::generate`], with values serialized as JSON.
+    fn generate(
+        &self,
+        existing_partial: Option<serde_json::Value>,
+        dependent_settings: Option<serde_json::Value>,
+    ) -> Result<GenerateResult<serde_json::Value, serde_json::Value>, BottlerocketSettingError>;
+
+    /// See [`SettingsModel::validate`], with `value` as the raw text given to the extension,
+    /// encoded in `format`.
+    ///
+    /// When `lenient` is set and `format` is [`Format::Json`], `value` may use JSONC-style
+    /// comments and trailing commas; see [`super::input::parse_value`].
+    fn validate(
+        &self,
+        value: &str,
+        format: Format,
+        lenient: bool,
+        validated_settings: Option<serde_json::Value>,
+    ) -> Result<bool, BottlerocketSettingError>;
+
+    /// Deep-merges `patch` (encoded in `format`) into `current_value` and runs
+    /// [`SettingsModel::set`] on the result.
+    ///
+    /// A JSON `null` in `patch` means "leave this field unchanged" rather than overwrite it, so
+    /// callers can submit only the fields they want to change. See
+    /// [`merge_non_null_json_value_into`].
+    fn set_partial(
+        &self,
+        current_value: Option<serde_json::Value>,
+        patch: &str,
+        format: Format,
+        lenient: bool,
+    ) -> Result<serde_json::Value, BottlerocketSettingError>;
+
+    /// Generates a JSON Schema describing valid values for this version of the model.
+    ///
+    /// Fails with [`BottlerocketSettingError::NoSchema`] if this model was registered via
+    /// [`BottlerocketSetting::model`] rather than
+    /// [`BottlerocketSetting::model_with_schema`](super::BottlerocketSetting::model_with_schema).
+    fn schema(
+        &self,
+        params: SchemaParams,
+    ) -> Result<schemars::schema::RootSchema, BottlerocketSettingError>;
+}
+
+/// Converts a boxed, concretely-typed model into its type-erased form.
+///
+/// This is implemented for every [`BottlerocketSetting<T>`] whose `T` implements
+/// [`SettingsModel`], which lets [`with_models`](crate::NullMigratorExtensionBuilder::with_models)
+/// accept a heterogeneous list of model versions. Schema generation is a separate, optional
+/// capability (see [`BottlerocketSetting::model_with_schema`]) — `T` need not implement
+/// [`SettingsModelSchema`](super::SettingsModelSchema) to be erased.
+pub trait AsTypeErasedModel {
+    /// Erases the concrete type of `self`.
+    fn as_erased_model(self: Box<Self>) -> Box<dyn TypeErasedModel>;
+}
+
+impl<T> AsTypeErasedModel for BottlerocketSetting<T>
+where
+    T: SettingsModel + 'static,
+{
+    fn as_erased_model(self: Box<Self>) -> Box<dyn TypeErasedModel> {
+        self
+    }
+}
+
+impl<T> TypeErasedModel for BottlerocketSetting<T>
+where
+    T: SettingsModel,
+{
+    fn get_version(&self) -> &'static str {
+        T::get_version()
+    }
+
+    fn dependencies(&self) -> &'static [Dependency] {
+        T::dependencies()
+    }
+
+    fn set(
+        &self,
+        current_value: Option<serde_json::Value>,
+        target: &str,
+        format: Format,
+        lenient: bool,
+    ) -> Result<serde_json::Value, BottlerocketSettingError> {
+        let version = T::get_version();
+
+        let current_value: Option<T> = current_value
+            .clone()
+            .map(serde_json::from_value)
+            .transpose()
+            .context(DeserializeInputSnafu {
+                input_type: "current",
+                input: current_value.unwrap_or_default(),
+                version,
+                format: INTERNAL_FORMAT.as_str(),
+            })?;
+        let target = format.decode(target, lenient).context(ParseSettingSnafu {
+            version,
+            format: format.as_str(),
+        })?;
+        let target_value: T =
+            serde_json::from_value(target.clone()).context(DeserializeInputSnafu {
+                input_type: "target",
+                input: target,
+                version,
+                format: format.as_str(),
+            })?;
+
+        let result = T::set(current_value, target_value)
+            .map_err(Into::into)
+            .context(SetSettingSnafu { version })?;
+
+        serde_json::to_value(result).context(SerializeResultSnafu {
+            version,
+            operation: "set",
+            format: INTERNAL_FORMAT.as_str(),
+        })
+    }
+
+    fn set_partial(
+        &self,
+        current_value: Option<serde_json::Value>,
+        patch: &str,
+        format: Format,
+        lenient: bool,
+    ) -> Result<serde_json::Value, BottlerocketSettingError> {
+        let version = T::get_version();
+
+        let patch = format.decode(patch, lenient).context(ParseSettingSnafu {
+            version,
+            format: format.as_str(),
+        })?;
+
+        let mut merged = current_value
+            .clone()
+            .unwrap_or_else(|| serde_json::Value::Object(Default::default()));
+        merge_non_null_json_value_into(patch, &mut merged);
+
+        let target_value: T =
+            serde_json::from_value(merged.clone()).context(DeserializeInputSnafu {
+                input_type: "target",
+                input: merged,
+                version,
+                format: format.as_str(),
+            })?;
+
+        let current_value: Option<T> = current_value
+            .clone()
+            .map(serde_json::from_value)
+            .transpose()
+            .context(DeserializeInputSnafu {
+                input_type: "current",
+                input: current_value.unwrap_or_default(),
+                version,
+                format: INTERNAL_FORMAT.as_str(),
+            })?;
+
+        let result = T::set(current_value, target_value)
+            .map_err(Into::into)
+            .context(SetSettingSnafu { version })?;
+
+        serde_json::to_value(result).context(SerializeResultSnafu {
+            version,
+            operation: "set",
+            format: INTERNAL_FORMAT.as_str(),
+        })
+    }
+
+    fn generate(
+        &self,
+        existing_partial: Option<serde_json::Value>,
+        dependent_settings: Option<serde_json::Value>,
+    ) -> Result<GenerateResult<serde_json::Value, serde_json::Value>, BottlerocketSettingError>
+    {
+        let version = T::get_version();
+
+        let existing_partial: Option<T::PartialKind> = existing_partial
+            .clone()
+            .map(serde_json::from_value)
+            .transpose()
+            .context(DeserializeInputSnafu {
+                input_type: "existing_partial",
+                input: existing_partial.unwrap_or_default(),
+                version,
+                format: INTERNAL_FORMAT.as_str(),
+            })?;
+
+        let result = T::generate(existing_partial, Dependencies::new(dependent_settings))
+            .map_err(Into::into)
+            .context(GenerateSettingSnafu { version })?;
+
+        result.serialize().context(SerializeResultSnafu {
+            version,
+            operation: "generate",
+            format: INTERNAL_FORMAT.as_str(),
+        })
+    }
+
+    fn validate(
+        &self,
+        value: &str,
+        format: Format,
+        lenient: bool,
+        validated_settings: Option<serde_json::Value>,
+    ) -> Result<bool, BottlerocketSettingError> {
+        let version = T::get_version();
+
+        let value = format.decode(value, lenient).context(ParseSettingSnafu {
+            version,
+            format: format.as_str(),
+        })?;
+        let value: T = serde_json::from_value(value.clone()).context(DeserializeInputSnafu {
+            input_type: "value",
+            input: value,
+            version,
+            format: format.as_str(),
+        })?;
+
+        T::validate(value, Dependencies::new(validated_settings))
+            .map_err(Into::into)
+            .context(ValidateSettingSnafu { version })
+    }
+
+    fn schema(
+        &self,
+        params: SchemaParams,
+    ) -> Result<schemars::schema::RootSchema, BottlerocketSettingError> {
+        self.schema_fn
+            .map(|schema_fn| schema_fn(&params))
+            .context(NoSchemaSnafu {
+                version: T::get_version(),
+            })
+    }
+}
@@ -0,0 +1,107 @@
+//! Wire formats for extension input and output.
+//!
+//! Bottlerocket settings are authored in TOML, while other embedders (see the PowerTools RON
+//! migration) prefer RON, and some tooling simply prefers JSON. [`Format`] lets an extension
+//! accept and emit whichever of these its input actually arrives in; models themselves stay
+//! oblivious to the choice, since [`decode`](Format::decode) and [`encode`](Format::encode) both
+//! operate on plain [`serde_json::Value`], the same intermediate representation the rest of
+//! [`crate::model`] already deserializes models through.
+use serde::de::Error as _;
+
+/// The wire format used to encode and decode an extension's input and output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// Plain JSON, optionally with [lenient](super::input) parsing of the input.
+    Json,
+    /// TOML, the format Bottlerocket's own settings are authored in.
+    Toml,
+    /// [RON](https://github.com/ron-rs/ron), preferred by some embedders (e.g. PowerTools).
+    Ron,
+}
+
+impl Format {
+    /// Returns the name used in error messages, e.g. `"json"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Toml => "toml",
+            Format::Ron => "ron",
+        }
+    }
+
+    /// Decodes `input` into a [`serde_json::Value`], using this format's text syntax.
+    ///
+    /// `lenient` only affects [`Format::Json`], where it allows JSONC-style comments and trailing
+    /// commas; see [`super::input::parse_value`].
+    pub fn decode(&self, input: &str, lenient: bool) -> serde_json::Result<serde_json::Value> {
+        match self {
+            Format::Json => super::input::parse_value(input, lenient),
+            Format::Toml => {
+                let value: toml::Value =
+                    toml::from_str(input).map_err(serde_json::Error::custom)?;
+                serde_json::to_value(value)
+            }
+            Format::Ron => {
+                let value: ron::Value = ron::from_str(input).map_err(serde_json::Error::custom)?;
+                serde_json::to_value(value)
+            }
+        }
+    }
+
+    /// Encodes `value` into this format's text representation.
+    pub fn encode(&self, value: &serde_json::Value) -> serde_json::Result<String> {
+        match self {
+            Format::Json => serde_json::to_string_pretty(value),
+            Format::Toml => toml::to_string_pretty(value).map_err(serde_json::Error::custom),
+            Format::Ron => ron::ser::to_string_pretty(value, Default::default())
+                .map_err(serde_json::Error::custom),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // TOML has no top-level scalars or nulls, so round-trip tests use an object value that's
+    // representable in all three formats.
+    fn sample() -> serde_json::Value {
+        json!({"name": "example", "count": 3})
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let value = sample();
+        let encoded = Format::Json.encode(&value).unwrap();
+        assert_eq!(Format::Json.decode(&encoded, false).unwrap(), value);
+    }
+
+    #[test]
+    fn toml_round_trips() {
+        let value = sample();
+        let encoded = Format::Toml.encode(&value).unwrap();
+        assert_eq!(Format::Toml.decode(&encoded, false).unwrap(), value);
+    }
+
+    #[test]
+    fn ron_round_trips() {
+        let value = sample();
+        let encoded = Format::Ron.encode(&value).unwrap();
+        assert_eq!(Format::Ron.decode(&encoded, false).unwrap(), value);
+    }
+
+    #[test]
+    fn as_str_matches_format_name() {
+        assert_eq!(Format::Json.as_str(), "json");
+        assert_eq!(Format::Toml.as_str(), "toml");
+        assert_eq!(Format::Ron.as_str(), "ron");
+    }
+
+    #[test]
+    fn json_decode_is_lenient_only_when_requested() {
+        let input = r#"{"name": "example", "count": 3,}"#;
+        assert!(Format::Json.decode(input, false).is_err());
+        assert_eq!(Format::Json.decode(input, true).unwrap(), sample());
+    }
+}
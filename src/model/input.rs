@@ -0,0 +1,172 @@
+//! Lenient parsing of hand-authored settings input.
+//!
+//! This module is private to [`crate::model`]; callers reach it indirectly through
+//! [`TypeErasedModel::set`](super::TypeErasedModel::set) and
+//! [`TypeErasedModel::validate`](super::TypeErasedModel::validate).
+//!
+//! Settings payloads are often hand-written or produced by templating, and authors reach for
+//! `// comments` and trailing commas even though plain JSON allows neither. This module provides
+//! an opt-in JSONC-style parsing mode, modeled on the lenient parser Zed uses for its settings
+//! files: comments and trailing commas are stripped before the text is handed to `serde_json`,
+//! producing the same [`serde_json::Value`] that strict parsing would have, so nothing downstream
+//! needs to know which mode was used.
+
+/// Parses `input` as JSON, optionally tolerating `//`/`/* */` comments and trailing commas.
+///
+/// When `lenient` is `false` this is exactly `serde_json::from_str`. When `true`, comments and
+/// trailing commas are stripped first so that hand-authored or templated input isn't rejected for
+/// stylistic reasons.
+pub fn parse_value(input: &str, lenient: bool) -> serde_json::Result<serde_json::Value> {
+    if lenient {
+        serde_json::from_str(&strip_jsonc(input))
+    } else {
+        serde_json::from_str(input)
+    }
+}
+
+/// Strips `//` line comments and `/* */` block comments from `input`, leaving string literals
+/// untouched.
+fn strip_comments(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                in_string = !in_string;
+                out.push(c);
+            }
+            '\\' if in_string => {
+                out.push(c);
+                if let Some(escaped) = chars.next() {
+                    out.push(escaped);
+                }
+            }
+            '/' if !in_string && chars.peek() == Some(&'/') => {
+                chars.next();
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            '/' if !in_string && chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = '\0';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
+/// Removes commas that are followed only by whitespace before a closing `}` or `]`.
+fn strip_trailing_commas(input: &str) -> String {
+    let mut out: Vec<char> = Vec::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                in_string = !in_string;
+                out.push(c);
+            }
+            '\\' if in_string => {
+                out.push(c);
+                if let Some(escaped) = chars.next() {
+                    out.push(escaped);
+                }
+            }
+            '}' | ']' if !in_string => {
+                if let Some(comma_index) = out
+                    .iter()
+                    .rposition(|c| !c.is_whitespace())
+                    .filter(|&i| out[i] == ',')
+                {
+                    out.remove(comma_index);
+                }
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+
+    out.into_iter().collect()
+}
+
+/// Strips JSONC-style comments and trailing commas, producing plain JSON text.
+fn strip_jsonc(input: &str) -> String {
+    strip_trailing_commas(&strip_comments(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn strict_parsing_rejects_comments() {
+        assert!(parse_value("{ \"a\": 1 /* nope */ }", false).is_err());
+    }
+
+    #[test]
+    fn lenient_parsing_strips_line_comments() {
+        let input = r#"{
+            // this is a comment
+            "a": 1
+        }"#;
+        assert_eq!(parse_value(input, true).unwrap(), json!({"a": 1}));
+    }
+
+    #[test]
+    fn lenient_parsing_strips_block_comments() {
+        let input = r#"{ /* leading */ "a": 1 /* trailing */ }"#;
+        assert_eq!(parse_value(input, true).unwrap(), json!({"a": 1}));
+    }
+
+    #[test]
+    fn lenient_parsing_strips_trailing_commas() {
+        let input = r#"{"a": 1, "b": [1, 2, 3,],}"#;
+        assert_eq!(
+            parse_value(input, true).unwrap(),
+            json!({"a": 1, "b": [1, 2, 3]})
+        );
+    }
+
+    #[test]
+    fn lenient_parsing_leaves_string_contents_alone() {
+        let input = r#"{"a": "not // a comment, not a trailing comma,"}"#;
+        assert_eq!(
+            parse_value(input, true).unwrap(),
+            json!({"a": "not // a comment, not a trailing comma,"})
+        );
+    }
+
+    #[test]
+    fn lenient_parsing_handles_escaped_quotes_before_trailing_comma() {
+        let input = r#"{"a": "x\"y", "b": 1,}"#;
+        assert_eq!(
+            parse_value(input, true).unwrap(),
+            json!({"a": "x\"y", "b": 1})
+        );
+    }
+
+    #[test]
+    fn lenient_parsing_still_accepts_strict_json() {
+        let input = r#"{"a": 1, "b": [1, 2, 3]}"#;
+        assert_eq!(
+            parse_value(input, true).unwrap(),
+            parse_value(input, false).unwrap()
+        );
+    }
+}
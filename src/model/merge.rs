@@ -0,0 +1,98 @@
+//! Recursive, non-destructive merging of JSON values for partial settings updates.
+
+/// Deep-merges `patch` into `base`.
+///
+/// When both `patch` and `base` are JSON objects at a given path, they're merged key-by-key;
+/// otherwise `patch` simply overwrites `base`. A JSON `null` anywhere in `patch` means "leave this
+/// field unchanged" rather than "clear it", so omitted and explicitly-null fields behave the same
+/// way: the value already in `base` is preserved.
+///
+/// This mirrors Zed's `merge_non_null_json_value_into`, which settings extensions can lean on to
+/// offer partial-update semantics without forcing callers to resubmit a setting's entire value.
+pub fn merge_non_null_json_value_into(patch: serde_json::Value, base: &mut serde_json::Value) {
+    use serde_json::Value;
+
+    match patch {
+        Value::Object(patch) if base.is_object() => {
+            let base = base.as_object_mut().expect("checked by guard above");
+            for (key, patch_value) in patch {
+                if patch_value.is_null() {
+                    continue;
+                }
+                match base.get_mut(&key) {
+                    Some(base_value) => merge_non_null_json_value_into(patch_value, base_value),
+                    None => {
+                        base.insert(key, strip_nulls(patch_value));
+                    }
+                }
+            }
+        }
+        patch_value => {
+            if !patch_value.is_null() {
+                *base = strip_nulls(patch_value);
+            }
+        }
+    }
+}
+
+/// Recursively drops `null`-valued object keys from `value`.
+///
+/// Used when a patch introduces a key that doesn't yet exist in `base`, or replaces a non-object
+/// value in `base` with an object: since there's no existing object to merge against, the patch
+/// subtree is inserted wholesale, so any `null`s nested inside it (each meaning "leave unchanged")
+/// need to be stripped here instead of by the key-by-key merge above.
+fn strip_nulls(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.into_iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| (key, strip_nulls(value)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn null_in_patch_leaves_existing_field_unchanged() {
+        let mut base = json!({"a": 1, "b": 2});
+        merge_non_null_json_value_into(json!({"a": null, "b": 3}), &mut base);
+        assert_eq!(base, json!({"a": 1, "b": 3}));
+    }
+
+    #[test]
+    fn nested_objects_are_merged_key_by_key() {
+        let mut base = json!({"outer": {"x": 1, "y": 2}});
+        merge_non_null_json_value_into(json!({"outer": {"x": null, "z": 3}}), &mut base);
+        assert_eq!(base, json!({"outer": {"x": 1, "y": 2, "z": 3}}));
+    }
+
+    #[test]
+    fn non_object_patch_overwrites_base() {
+        let mut base = json!({"a": 1});
+        merge_non_null_json_value_into(json!("replacement"), &mut base);
+        assert_eq!(base, json!("replacement"));
+    }
+
+    #[test]
+    fn absent_key_strips_nested_nulls_instead_of_inserting_them() {
+        // Regression: when `base` doesn't already contain a key, the patch subtree used to be
+        // inserted verbatim, leaving a literal `null` in the merged document for a field the
+        // patch never intended to touch.
+        let mut base = json!({});
+        merge_non_null_json_value_into(json!({"outer": {"inner": null, "x": 1}}), &mut base);
+        assert_eq!(base, json!({"outer": {"x": 1}}));
+    }
+
+    #[test]
+    fn top_level_null_patch_is_a_no_op() {
+        let mut base = json!({"a": 1});
+        merge_non_null_json_value_into(serde_json::Value::Null, &mut base);
+        assert_eq!(base, json!({"a": 1}));
+    }
+}
@@ -4,10 +4,19 @@ use serde::Deserialize;
 use serde::{de::DeserializeOwned, Serialize};
 use std::{fmt::Debug, marker::PhantomData};
 
+mod dependency;
 #[doc(hidden)]
 pub mod erased;
+mod format;
+mod input;
+mod merge;
+mod schema;
+
+pub use dependency::{Dependencies, Dependency};
 pub use erased::{AsTypeErasedModel, TypeErasedModel};
 pub use error::BottlerocketSettingError;
+pub use format::Format;
+pub use schema::{SchemaParams, SettingsModelSchema};
 
 /// This trait is required to model new settings in the Bottlerocket API using the settings SDK.
 ///
@@ -17,7 +26,7 @@ pub use error::BottlerocketSettingError;
 ///
 /// ```
 /// # use anyhow::Result;
-/// # use bottlerocket_settings_sdk::{SettingsModel, GenerateResult};
+/// # use bottlerocket_settings_sdk::{SettingsModel, GenerateResult, Dependencies};
 /// # use serde::{Serialize, Deserialize};
 /// # use std::convert::Infallible;
 ///
@@ -45,13 +54,13 @@ pub use error::BottlerocketSettingError;
 ///
 ///     fn generate(
 ///         _: Option<Self::PartialKind>,
-///         _: Option<serde_json::Value>,
+///         _: Dependencies,
 ///     ) -> Result<GenerateResult<Self::PartialKind, Self>> {
 ///         // Dynamic generation of the value occurs here...
 ///         Ok(GenerateResult::Complete(MySettings::default()))
 ///     }
 ///
-///     fn validate(_value: Self, _validated_settings: Option<serde_json::Value>) -> Result<bool> {
+///     fn validate(_value: Self, _validated_settings: Dependencies) -> Result<bool> {
 ///         // Cross-validation of new values can occur against other settings here...
 ///         Ok(true)
 ///     }
@@ -74,6 +83,17 @@ pub trait SettingsModel: Sized + Serialize + DeserializeOwned + Debug {
     /// Returns the version of this settings model, e.g. "v1".
     fn get_version() -> &'static str;
 
+    /// Declares the other settings extensions (and versions) this model depends on, e.g. because
+    /// `generate` computes its value from them.
+    ///
+    /// The settings system uses this to order `generate` cycles so that dependencies are
+    /// generated first, and the SDK uses it to give `generate`/`validate` strongly-typed access to
+    /// those extensions' values through the [`Dependencies`] they're passed. Models with no
+    /// dependencies can leave this at its default, empty implementation.
+    fn dependencies() -> &'static [Dependency] {
+        &[]
+    }
+
     /// Determines whether this setting can be set to the `target` value, given its current value.
     ///
     /// The returned value is what is ultimately set in the settings datastore. While this leaves
@@ -85,21 +105,18 @@ pub trait SettingsModel: Sized + Serialize + DeserializeOwned + Debug {
     ///
     /// The settings system repeatedly invokes `generate` on all settings until they have
     /// completed. On each generation cycle, the settings extension is provided any values that it
-    /// has previously generated, as well as all of the data that has thus far been generated by its
-    /// dependencies.
+    /// has previously generated, as well as typed access to all of the data that has thus far been
+    /// generated by the extensions declared in [`dependencies`](SettingsModel::dependencies).
     fn generate(
         existing_partial: Option<Self::PartialKind>,
-        dependent_settings: Option<serde_json::Value>,
+        dependent_settings: Dependencies,
     ) -> Result<GenerateResult<Self::PartialKind, Self>, Self::ErrorKind>;
 
     /// Validates this setting, allowing for cross-validation with other settings.
     ///
-    /// Cross-validated settings are provided as a JSON Map, where the key is the extension name and
-    /// the value is the value of that setting.
-    fn validate(
-        _value: Self,
-        _validated_settings: Option<serde_json::Value>,
-    ) -> Result<bool, Self::ErrorKind>;
+    /// `validated_settings` gives typed access to the current values of the extensions declared in
+    /// [`dependencies`](SettingsModel::dependencies), via [`Dependencies::get`].
+    fn validate(_value: Self, _validated_settings: Dependencies) -> Result<bool, Self::ErrorKind>;
 }
 
 /// This struct wraps [`SettingsModel`]s in a referencable object which is passed to the
@@ -120,13 +137,33 @@ pub trait SettingsModel: Sized + Serialize + DeserializeOwned + Debug {
 #[derive(Debug, Hash, Eq, PartialEq, PartialOrd, Ord, Default)]
 pub struct BottlerocketSetting<T: SettingsModel> {
     _ghost: PhantomData<T>,
+    schema_fn: Option<fn(&SchemaParams) -> schemars::schema::RootSchema>,
 }
 
 impl<T: SettingsModel> BottlerocketSetting<T> {
     /// Boxes the object so that it can be used in the settings SDK as a `Box<dyn Model>`.
+    ///
+    /// The resulting model doesn't support JSON Schema generation; use
+    /// [`model_with_schema`](Self::model_with_schema) for a `T` that implements
+    /// [`SettingsModelSchema`].
     pub fn model() -> Box<Self> {
         Box::new(Self {
             _ghost: PhantomData,
+            schema_fn: None,
+        })
+    }
+}
+
+impl<T: SettingsModelSchema> BottlerocketSetting<T> {
+    /// Boxes the object the same way [`model`](Self::model) does, additionally opting this
+    /// version in to JSON Schema generation via `T`'s [`SettingsModelSchema`] implementation.
+    ///
+    /// Schema generation is opt-in rather than required of every model, since not every `T` that
+    /// implements [`SettingsModel`] also implements [`schemars::JsonSchema`].
+    pub fn model_with_schema() -> Box<Self> {
+        Box::new(Self {
+            _ghost: PhantomData,
+            schema_fn: Some(T::schema),
         })
     }
 }
@@ -179,8 +216,9 @@ mod error {
     #[snafu(visibility(pub))]
     pub enum BottlerocketSettingError {
         #[snafu(display(
-            "Failed to deserialize '{}' input as settings value version '{}': {}\nValue: {}",
+            "Failed to deserialize '{}' {} input as settings value version '{}': {}\nValue: {}",
             input_type,
+            format,
             version,
             source,
             serde_json::to_string_pretty(&input).unwrap_or(input.to_string()),
@@ -189,6 +227,7 @@ mod error {
             input_type: &'static str,
             input: serde_json::Value,
             version: &'static str,
+            format: &'static str,
             source: serde_json::Error,
         },
 
@@ -203,24 +242,28 @@ mod error {
         },
 
         #[snafu(display(
-            "Failed to parse setting value (version '{}') from JSON: {}",
+            "Failed to parse setting value (version '{}') from {}: {}",
             version,
+            format,
             source
         ))]
         ParseSetting {
             version: &'static str,
+            format: &'static str,
             source: serde_json::Error,
         },
 
         #[snafu(display(
-            "Failed to serialize settings extension (version '{}') '{}' result: {}",
+            "Failed to serialize settings extension (version '{}') '{}' result as {}: {}",
             version,
             operation,
+            format,
             source
         ))]
         SerializeResult {
             version: &'static str,
             operation: &'static str,
+            format: &'static str,
             source: serde_json::Error,
         },
 
@@ -239,5 +282,30 @@ mod error {
             version: &'static str,
             source: Box<dyn std::error::Error + Send + Sync + 'static>,
         },
+
+        #[snafu(display("Missing required dependency '{}' (version '{}')", extension, version))]
+        MissingDependency {
+            extension: &'static str,
+            version: &'static str,
+        },
+
+        #[snafu(display(
+            "Failed to deserialize dependency '{}' (version '{}'): {}",
+            extension,
+            version,
+            source
+        ))]
+        DependencyDeserialize {
+            extension: &'static str,
+            version: &'static str,
+            source: serde_json::Error,
+        },
+
+        #[snafu(display(
+            "Settings model version '{}' was not registered with schema support; use \
+             `BottlerocketSetting::model_with_schema` instead of `model` to enable it",
+            version
+        ))]
+        NoSchema { version: &'static str },
     }
 }
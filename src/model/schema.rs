@@ -0,0 +1,92 @@
+//! JSON Schema generation for [`SettingsModel`]s.
+use crate::model::SettingsModel;
+use std::collections::HashMap;
+
+/// Extra context passed to [`SettingsModelSchema::schema`] so a model can refine the schema it
+/// emits — for example, enumerating the valid values of a field that is constrained by another
+/// setting's current value.
+///
+/// This mirrors the parameterized schema generation used by Zed's settings store, where a
+/// setting's schema can depend on state that isn't known until schema-generation time.
+#[derive(Debug, Default, Clone)]
+pub struct SchemaParams {
+    /// Known-valid values for fields that are constrained by another setting, keyed by field
+    /// name.
+    pub known_values: HashMap<String, Vec<serde_json::Value>>,
+}
+
+/// Allows a [`SettingsModel`] to describe its own shape as a JSON Schema.
+///
+/// Any model that implements [`schemars::JsonSchema`] gets this for free via the blanket
+/// implementation below, which describes the model itself and ignores [`SchemaParams`]. Models whose
+/// valid values depend on runtime state (e.g. another setting's current value) can implement this
+/// directly and use [`SchemaParams`] to tighten the schema they emit, the way `KubernetesSettingsV1`
+/// would enumerate the node taints it knows are valid.
+pub trait SettingsModelSchema: SettingsModel {
+    /// Generates a JSON Schema describing valid values for this settings model.
+    fn schema(params: &SchemaParams) -> schemars::schema::RootSchema;
+}
+
+impl<T> SettingsModelSchema for T
+where
+    T: SettingsModel + schemars::JsonSchema,
+{
+    fn schema(_params: &SchemaParams) -> schemars::schema::RootSchema {
+        schemars::schema_for!(T)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Dependencies;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+    struct TestSetting {
+        name: String,
+        count: i64,
+    }
+
+    impl SettingsModel for TestSetting {
+        type PartialKind = Self;
+        type ErrorKind = std::convert::Infallible;
+
+        fn get_version() -> &'static str {
+            "v1"
+        }
+
+        fn set(_current_value: Option<Self>, target: Self) -> Result<Self, Self::ErrorKind> {
+            Ok(target)
+        }
+
+        fn generate(
+            _existing_partial: Option<Self::PartialKind>,
+            _dependent_settings: Dependencies,
+        ) -> Result<super::super::GenerateResult<Self::PartialKind, Self>, Self::ErrorKind>
+        {
+            unimplemented!()
+        }
+
+        fn validate(
+            _value: Self,
+            _validated_settings: Dependencies,
+        ) -> Result<bool, Self::ErrorKind> {
+            Ok(true)
+        }
+    }
+
+    #[test]
+    fn blanket_impl_generates_schema_for_fields() {
+        let schema = TestSetting::schema(&SchemaParams::default());
+        let schema_json = serde_json::to_value(&schema).unwrap();
+        let properties = &schema_json["properties"];
+        assert!(properties.get("name").is_some());
+        assert!(properties.get("count").is_some());
+    }
+
+    #[test]
+    fn schema_params_default_has_no_known_values() {
+        assert!(SchemaParams::default().known_values.is_empty());
+    }
+}